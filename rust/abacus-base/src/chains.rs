@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+/// Tunables for a chain's sync and connectivity health-check tasks.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexSettings {
+    /// Block height to start syncing from when no checkpoint exists.
+    pub from: u32,
+    /// How often the sync task polls the indexer for new messages.
+    pub poll_interval: Duration,
+    /// How often the connectivity health check pings the mailbox.
+    pub health_check_interval: Duration,
+    /// Consecutive failed health checks before the mailbox is reconnected.
+    pub health_check_failure_threshold: u32,
+}
+
+impl Default for IndexSettings {
+    fn default() -> Self {
+        Self {
+            from: 0,
+            poll_interval: Duration::from_secs(5),
+            health_check_interval: Duration::from_secs(30),
+            health_check_failure_threshold: 3,
+        }
+    }
+}
+
+impl IndexSettings {
+    /// Override the block height to start syncing from.
+    pub fn with_from(mut self, from: u32) -> Self {
+        self.from = from;
+        self
+    }
+}
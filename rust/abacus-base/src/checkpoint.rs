@@ -0,0 +1,209 @@
+use std::time::{Duration, Instant};
+
+use ethers::core::types::H256;
+use eyre::{eyre, Result};
+
+use crate::db::AbacusDB;
+use crate::kv_store::{Decode, Encode};
+
+/// Minimum interval between checkpoints, regardless of indexing volume.
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Minimum number of newly indexed messages before a checkpoint is taken.
+pub const CHECKPOINT_MIN_OPS: u32 = 16;
+
+/// Number of past checkpoints to retain; older ones are garbage collected.
+pub const CHECKPOINT_RETENTION: usize = 5;
+
+const CHECKPOINT_PREFIX: &[u8] = b"checkpoint_";
+
+/// A compact snapshot of the synced state of a [`CachingMailbox`](crate::CachingMailbox).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexCheckpoint {
+    /// Last fully-indexed block height.
+    pub block: u32,
+    /// Total number of leaves (dispatched messages) indexed as of `block`.
+    pub leaf_count: u32,
+    /// Merkle root summarizing all leaves indexed as of `block`.
+    pub root: H256,
+}
+
+impl Encode for IndexCheckpoint {
+    fn to_vec(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(&self.block.to_be_bytes());
+        bytes.extend_from_slice(&self.leaf_count.to_be_bytes());
+        bytes.extend_from_slice(self.root.as_bytes());
+        bytes
+    }
+}
+
+impl Decode for IndexCheckpoint {
+    fn read_from(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 40 {
+            return Err(eyre!("expected 40 bytes, got {}", bytes.len()));
+        }
+        Ok(Self {
+            block: u32::from_be_bytes(bytes[0..4].try_into().unwrap()),
+            leaf_count: u32::from_be_bytes(bytes[4..8].try_into().unwrap()),
+            root: H256::from_slice(&bytes[8..40]),
+        })
+    }
+}
+
+/// Persists [`IndexCheckpoint`]s to an [`AbacusDB`], rate-limited by
+/// [`CHECKPOINT_INTERVAL`] and [`CHECKPOINT_MIN_OPS`].
+#[derive(Debug)]
+pub struct CheckpointManager {
+    db: AbacusDB,
+    ops_since_checkpoint: u32,
+    last_checkpoint_at: Instant,
+}
+
+impl CheckpointManager {
+    /// Instantiate a new CheckpointManager over `db`.
+    pub fn new(db: AbacusDB) -> Self {
+        Self {
+            db,
+            ops_since_checkpoint: 0,
+            last_checkpoint_at: Instant::now(),
+        }
+    }
+
+    /// Load the most recent checkpoint, if any.
+    pub async fn load_latest(&self) -> Result<Option<IndexCheckpoint>> {
+        match self.latest_checkpointed_block().await? {
+            Some(block) => {
+                self.db
+                    .retrieve_decodable(CHECKPOINT_PREFIX, &block.to_be_bytes())
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Record that `new_ops` additional messages have been indexed, and
+    /// persist `checkpoint` if both the interval and min-ops guards are
+    /// satisfied. Returns whether a checkpoint was taken.
+    pub async fn record_and_maybe_checkpoint(
+        &mut self,
+        new_ops: u32,
+        checkpoint: IndexCheckpoint,
+    ) -> Result<bool> {
+        self.ops_since_checkpoint += new_ops;
+        if !self.should_checkpoint() {
+            return Ok(false);
+        }
+
+        self.db
+            .store_encodable(CHECKPOINT_PREFIX, &checkpoint.block.to_be_bytes(), &checkpoint)
+            .await?;
+        self.gc_checkpoints().await?;
+        self.ops_since_checkpoint = 0;
+        self.last_checkpoint_at = Instant::now();
+        Ok(true)
+    }
+
+    fn should_checkpoint(&self) -> bool {
+        self.ops_since_checkpoint >= CHECKPOINT_MIN_OPS
+            && self.last_checkpoint_at.elapsed() >= CHECKPOINT_INTERVAL
+    }
+
+    async fn latest_checkpointed_block(&self) -> Result<Option<u32>> {
+        let mut blocks = self.checkpointed_blocks().await?;
+        blocks.sort_unstable();
+        Ok(blocks.last().copied())
+    }
+
+    async fn gc_checkpoints(&self) -> Result<()> {
+        let mut blocks = self.checkpointed_blocks().await?;
+        blocks.sort_unstable();
+        if blocks.len() > CHECKPOINT_RETENTION {
+            for block in &blocks[..blocks.len() - CHECKPOINT_RETENTION] {
+                self.db.delete(CHECKPOINT_PREFIX, &block.to_be_bytes()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn checkpointed_blocks(&self) -> Result<Vec<u32>> {
+        Ok(self
+            .db
+            .keys_with_prefix(CHECKPOINT_PREFIX)
+            .await?
+            .into_iter()
+            .filter_map(|key| u32::read_from(&key).ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::kv_store::MemoryKeyValueStore;
+
+    fn checkpoint(block: u32) -> IndexCheckpoint {
+        IndexCheckpoint {
+            block,
+            leaf_count: block,
+            root: H256::zero(),
+        }
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_encode_decode() {
+        let original = checkpoint(42);
+        assert_eq!(IndexCheckpoint::read_from(&original.to_vec()).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn does_not_checkpoint_below_min_ops() {
+        let db = AbacusDB::from_store(Arc::new(MemoryKeyValueStore::new()));
+        let mut manager = CheckpointManager::new(db);
+        // last_checkpoint_at was just set, so the interval guard would also
+        // block a checkpoint here, but min-ops should short-circuit first.
+        let took_checkpoint = manager
+            .record_and_maybe_checkpoint(CHECKPOINT_MIN_OPS - 1, checkpoint(1))
+            .await
+            .unwrap();
+        assert!(!took_checkpoint);
+        assert!(manager.load_latest().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn does_not_checkpoint_before_interval_elapses() {
+        let db = AbacusDB::from_store(Arc::new(MemoryKeyValueStore::new()));
+        let mut manager = CheckpointManager::new(db);
+        // Enough ops, but CHECKPOINT_INTERVAL (1h) has not elapsed.
+        let took_checkpoint = manager
+            .record_and_maybe_checkpoint(CHECKPOINT_MIN_OPS, checkpoint(1))
+            .await
+            .unwrap();
+        assert!(!took_checkpoint);
+    }
+
+    #[tokio::test]
+    async fn gc_retains_only_the_most_recent_checkpoints() {
+        let db = AbacusDB::from_store(Arc::new(MemoryKeyValueStore::new()));
+        let mut manager = CheckpointManager::new(db.clone());
+        manager.last_checkpoint_at = Instant::now() - CHECKPOINT_INTERVAL;
+
+        for block in 1..=(CHECKPOINT_RETENTION as u32 + 2) {
+            manager.ops_since_checkpoint = CHECKPOINT_MIN_OPS;
+            manager.last_checkpoint_at = Instant::now() - CHECKPOINT_INTERVAL;
+            let took_checkpoint = manager
+                .record_and_maybe_checkpoint(0, checkpoint(block))
+                .await
+                .unwrap();
+            assert!(took_checkpoint);
+        }
+
+        let remaining = manager.checkpointed_blocks().await.unwrap();
+        assert_eq!(remaining.len(), CHECKPOINT_RETENTION);
+
+        let latest = manager.load_latest().await.unwrap().unwrap();
+        assert_eq!(latest.block, CHECKPOINT_RETENTION as u32 + 2);
+    }
+}
@@ -0,0 +1,136 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use abacus_core::{AbacusMessage, MailboxIndexer};
+use eyre::Result;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::instrument::Instrumented;
+use tracing::{info_span, Instrument};
+
+use crate::chains::IndexSettings;
+use crate::checkpoint::{CheckpointManager, IndexCheckpoint};
+use crate::db::AbacusDB;
+
+/// Running totals for a chain's [`ContractSync`] task.
+#[derive(Debug, Clone, Default)]
+pub struct ContractSyncMetrics {
+    messages_indexed: Arc<AtomicU64>,
+}
+
+impl ContractSyncMetrics {
+    /// Total messages indexed since this metrics handle was created.
+    pub fn messages_indexed(&self) -> u64 {
+        self.messages_indexed.load(Ordering::Relaxed)
+    }
+}
+
+/// Streams dispatched messages from a [`MailboxIndexer`] into an
+/// [`AbacusDB`], notifying subscribers and checkpointing progress as it
+/// goes.
+#[derive(Debug, Clone)]
+pub struct ContractSync {
+    chain_name: String,
+    db: AbacusDB,
+    indexer: Arc<dyn MailboxIndexer>,
+    index_settings: IndexSettings,
+    metrics: ContractSyncMetrics,
+    new_message_tx: watch::Sender<u32>,
+}
+
+impl ContractSync {
+    /// Instantiate a new ContractSync.
+    pub fn new(
+        chain_name: String,
+        db: AbacusDB,
+        indexer: Arc<dyn MailboxIndexer>,
+        index_settings: IndexSettings,
+        metrics: ContractSyncMetrics,
+        new_message_tx: watch::Sender<u32>,
+    ) -> Self {
+        Self {
+            chain_name,
+            db,
+            indexer,
+            index_settings,
+            metrics,
+            new_message_tx,
+        }
+    }
+
+    /// Spawn the task that polls `indexer` for newly dispatched messages,
+    /// persists them to `db`, wakes any [`CachingMailbox::subscribe`](crate::CachingMailbox::subscribe)
+    /// callers, and checkpoints progress.
+    pub fn sync_dispatched_messages(&self) -> Instrumented<JoinHandle<Result<()>>> {
+        let span = info_span!("sync_dispatched_messages", chain = %self.chain_name);
+        let sync = self.clone();
+        tokio::spawn(async move { sync.run().await }).instrument(span)
+    }
+
+    async fn run(self) -> Result<()> {
+        let mut checkpoint_manager = CheckpointManager::new(self.db.clone());
+        let mut from = match checkpoint_manager.load_latest().await {
+            Ok(Some(checkpoint)) => {
+                tracing::info!(
+                    block = checkpoint.block,
+                    leaf_count = checkpoint.leaf_count,
+                    "Resuming sync from latest checkpoint"
+                );
+                checkpoint.block
+            }
+            Ok(None) => self.index_settings.from,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to load latest checkpoint, falling back to configured start height");
+                self.index_settings.from
+            }
+        };
+
+        let mut interval = tokio::time::interval(self.index_settings.poll_interval);
+        loop {
+            interval.tick().await;
+
+            let tip = self.indexer.get_finalized_block_number().await?;
+            if tip <= from {
+                continue;
+            }
+
+            let messages = self.indexer.fetch_sorted_messages(from, tip).await?;
+            if !messages.is_empty() {
+                self.index_messages(&mut checkpoint_manager, tip, &messages)
+                    .await?;
+            }
+            from = tip;
+        }
+    }
+
+    async fn index_messages(
+        &self,
+        checkpoint_manager: &mut CheckpointManager,
+        tip: u32,
+        messages: &[AbacusMessage],
+    ) -> Result<()> {
+        let mut leaf_index = self.db.leaf_count().await?;
+        for message in messages {
+            self.db.store_message(leaf_index, message).await?;
+            leaf_index += 1;
+        }
+
+        self.metrics
+            .messages_indexed
+            .fetch_add(messages.len() as u64, Ordering::Relaxed);
+
+        // Wake any subscribers now that these leaves are committed.
+        let _ = self.new_message_tx.send(leaf_index);
+
+        let checkpoint = IndexCheckpoint {
+            block: tip,
+            leaf_count: leaf_index,
+            root: self.indexer.latest_root().await?,
+        };
+        checkpoint_manager
+            .record_and_maybe_checkpoint(messages.len() as u32, checkpoint)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use eyre::Result;
+
+use crate::kv_store::{Decode, Encode, KeyValueStore};
+
+/// Database handle for a single mailbox's synced state, backed by a
+/// pluggable [`KeyValueStore`] rather than a hardcoded RocksDB instance.
+#[derive(Debug, Clone)]
+pub struct AbacusDB {
+    store: Arc<dyn KeyValueStore>,
+}
+
+impl AbacusDB {
+    /// Wrap `store` as an AbacusDB.
+    pub fn from_store(store: Arc<dyn KeyValueStore>) -> Self {
+        Self { store }
+    }
+
+    /// Store `value` under `prefix`/`key`.
+    pub async fn store_encodable(
+        &self,
+        prefix: &'static [u8],
+        key: &[u8],
+        value: &impl Encode,
+    ) -> Result<()> {
+        self.store
+            .write(&prefixed_key(prefix, key), &value.to_vec())
+            .await
+    }
+
+    /// Load the value stored under `prefix`/`key`, if any.
+    pub async fn retrieve_decodable<T: Decode>(
+        &self,
+        prefix: &'static [u8],
+        key: &[u8],
+    ) -> Result<Option<T>> {
+        match self.store.read(&prefixed_key(prefix, key)).await? {
+            Some(bytes) => Ok(Some(T::read_from(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete the value stored under `prefix`/`key`, if any.
+    pub async fn delete(&self, prefix: &'static [u8], key: &[u8]) -> Result<()> {
+        self.store.delete(&prefixed_key(prefix, key)).await
+    }
+
+    /// List all keys (with the prefix stripped) stored under `prefix`.
+    pub async fn keys_with_prefix(&self, prefix: &'static [u8]) -> Result<Vec<Vec<u8>>> {
+        Ok(self
+            .store
+            .iter_prefix(&prefix)
+            .await?
+            .into_iter()
+            .map(|(key, _)| key[prefix.len()..].to_vec())
+            .collect())
+    }
+
+    /// Store a dispatched message under its leaf index, and bump the
+    /// persisted leaf count.
+    pub async fn store_message(&self, leaf_index: u32, message: &impl Encode) -> Result<()> {
+        self.store_encodable(MESSAGE_PREFIX, &leaf_index.to_be_bytes(), message)
+            .await?;
+        self.store_encodable(&[], LEAF_COUNT_KEY, &(leaf_index + 1))
+            .await
+    }
+
+    /// Fetch the message stored at `leaf_index`, if any.
+    pub async fn message_by_leaf_index<T: Decode>(&self, leaf_index: u32) -> Result<Option<T>> {
+        self.retrieve_decodable(MESSAGE_PREFIX, &leaf_index.to_be_bytes())
+            .await
+    }
+
+    /// Number of messages stored so far.
+    pub async fn leaf_count(&self) -> Result<u32> {
+        Ok(self
+            .retrieve_decodable(&[], LEAF_COUNT_KEY)
+            .await?
+            .unwrap_or(0))
+    }
+}
+
+const MESSAGE_PREFIX: &[u8] = b"message_";
+const LEAF_COUNT_KEY: &[u8] = b"leaf_count";
+
+fn prefixed_key(prefix: &'static [u8], key: &[u8]) -> Vec<u8> {
+    [prefix, key].concat()
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers::types::{BlockNumber, FeeHistory, U256};
+use eyre::{eyre, Result};
+use tokio::sync::Mutex;
+
+/// How long a fetched fee-history window is considered fresh.
+const FEE_HISTORY_TTL: Duration = Duration::from_secs(15);
+
+/// Number of trailing blocks requested in each `eth_feeHistory` call.
+const FEE_HISTORY_BLOCK_COUNT: u64 = 20;
+
+/// Multiplier applied to the next block's base fee when computing
+/// `maxFeePerGas`.
+const BASE_FEE_MULTIPLIER: u64 = 2;
+
+/// Priority-fee percentile used when computing `maxPriorityFeePerGas`.
+const PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+
+/// A source of `eth_feeHistory` data for [`GasOracle`].
+#[async_trait]
+pub trait FeeHistoryProvider: std::fmt::Debug + Send + Sync {
+    /// Fetch base fees and reward percentiles for the trailing
+    /// `block_count` blocks.
+    async fn fee_history(&self, block_count: u64, reward_percentiles: &[f64]) -> Result<FeeHistory>;
+}
+
+#[async_trait]
+impl<M> FeeHistoryProvider for M
+where
+    M: Middleware + std::fmt::Debug,
+{
+    async fn fee_history(&self, block_count: u64, reward_percentiles: &[f64]) -> Result<FeeHistory> {
+        Middleware::fee_history(self, block_count, BlockNumber::Latest, reward_percentiles)
+            .await
+            .map_err(|error| eyre!(error.to_string()))
+    }
+}
+
+/// Computes EIP-1559 `maxPriorityFeePerGas`/`maxFeePerGas` suggestions from
+/// `eth_feeHistory`, caching the fetched window for a short TTL.
+#[derive(Debug)]
+pub struct GasOracle {
+    provider: Arc<dyn FeeHistoryProvider>,
+    cached: Mutex<Option<(Instant, FeeHistory)>>,
+}
+
+impl GasOracle {
+    /// Instantiate a new GasOracle over `provider`.
+    pub fn new(provider: Arc<dyn FeeHistoryProvider>) -> Self {
+        Self {
+            provider,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Return `(max_priority_fee_per_gas, max_fee_per_gas)` suggestions for
+    /// a transaction to be submitted imminently.
+    pub async fn suggest_eip1559_fees(&self) -> Result<(U256, U256)> {
+        let history = self.fee_history().await?;
+
+        let max_priority_fee_per_gas = median_reward(&history.reward)
+            .ok_or_else(|| eyre!("eth_feeHistory returned no reward data"))?;
+
+        let next_base_fee = *history
+            .base_fee_per_gas
+            .last()
+            .ok_or_else(|| eyre!("eth_feeHistory returned no base fee data"))?;
+        // Guard against a zero/missing base fee producing a max fee of zero.
+        let next_base_fee = next_base_fee.max(U256::one());
+
+        let max_fee_per_gas = next_base_fee
+            .saturating_mul(U256::from(BASE_FEE_MULTIPLIER))
+            .saturating_add(max_priority_fee_per_gas);
+
+        Ok((max_priority_fee_per_gas, max_fee_per_gas))
+    }
+
+    async fn fee_history(&self) -> Result<FeeHistory> {
+        let mut cached = self.cached.lock().await;
+        if let Some((fetched_at, history)) = cached.as_ref() {
+            if fetched_at.elapsed() < FEE_HISTORY_TTL {
+                return Ok(history.clone());
+            }
+        }
+
+        let history = self
+            .provider
+            .fee_history(FEE_HISTORY_BLOCK_COUNT, &[PRIORITY_FEE_PERCENTILE])
+            .await?;
+        *cached = Some((Instant::now(), history.clone()));
+        Ok(history)
+    }
+}
+
+/// Median of the per-block reward at the configured percentile, across the
+/// fetched window.
+fn median_reward(reward: &[Vec<U256>]) -> Option<U256> {
+    let mut rewards: Vec<U256> = reward.iter().filter_map(|block| block.first()).copied().collect();
+    if rewards.is_empty() {
+        return None;
+    }
+    rewards.sort();
+    Some(rewards[rewards.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reward_window(values: &[u64]) -> Vec<Vec<U256>> {
+        values.iter().map(|v| vec![U256::from(*v)]).collect()
+    }
+
+    #[test]
+    fn median_reward_of_odd_window() {
+        assert_eq!(median_reward(&reward_window(&[3, 1, 2])), Some(U256::from(2)));
+    }
+
+    #[test]
+    fn median_reward_of_even_window() {
+        // Even-length windows take the upper-middle element.
+        assert_eq!(median_reward(&reward_window(&[1, 2, 3, 4])), Some(U256::from(3)));
+    }
+
+    #[test]
+    fn median_reward_of_empty_window_is_none() {
+        assert_eq!(median_reward(&[]), None);
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::Debug;
+use std::io::Cursor;
+
+use abacus_core::AbacusMessage;
+use async_trait::async_trait;
+use eyre::Result;
+
+/// A type usable as a key in a [`KeyValueStore`].
+pub trait Key: AsRef<[u8]> + Debug + Send + Sync {}
+
+impl<T> Key for T where T: AsRef<[u8]> + Debug + Send + Sync {}
+
+/// A type that can be written to a [`KeyValueStore`].
+pub trait Encode {
+    /// Encode `self` to bytes.
+    fn to_vec(&self) -> Vec<u8>;
+}
+
+/// The inverse of [`Encode`].
+pub trait Decode: Sized {
+    /// Decode `self` from bytes previously produced by [`Encode::to_vec`].
+    fn read_from(bytes: &[u8]) -> Result<Self>;
+}
+
+impl Encode for u32 {
+    fn to_vec(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+}
+
+impl Decode for u32 {
+    fn read_from(bytes: &[u8]) -> Result<Self> {
+        let array: [u8; 4] = bytes
+            .try_into()
+            .map_err(|_| eyre::eyre!("expected 4 bytes, got {}", bytes.len()))?;
+        Ok(u32::from_be_bytes(array))
+    }
+}
+
+impl Encode for AbacusMessage {
+    fn to_vec(&self) -> Vec<u8> {
+        abacus_core::Encode::to_vec(self)
+    }
+}
+
+impl Decode for AbacusMessage {
+    fn read_from(bytes: &[u8]) -> Result<Self> {
+        abacus_core::Decode::read_from(&mut Cursor::new(bytes))
+            .map_err(|error| eyre::eyre!(error.to_string()))
+    }
+}
+
+/// Storage backend behind [`AbacusDB`](crate::db::AbacusDB).
+#[async_trait]
+pub trait KeyValueStore: Debug + Send + Sync {
+    /// Write `value` under `key`, overwriting any existing value.
+    async fn write(&self, key: &dyn Key, value: &[u8]) -> Result<()>;
+
+    /// Read the value stored under `key`, if any.
+    async fn read(&self, key: &dyn Key) -> Result<Option<Vec<u8>>>;
+
+    /// Delete the value stored under `key`, if any.
+    async fn delete(&self, key: &dyn Key) -> Result<()>;
+
+    /// Iterate over all key/value pairs whose key starts with `prefix`.
+    async fn iter_prefix(&self, prefix: &dyn Key) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+}
+
+/// An in-memory [`KeyValueStore`], for tests.
+#[derive(Debug, Default)]
+pub struct MemoryKeyValueStore {
+    map: std::sync::Mutex<std::collections::BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryKeyValueStore {
+    /// Instantiate a new, empty MemoryKeyValueStore.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl KeyValueStore for MemoryKeyValueStore {
+    async fn write(&self, key: &dyn Key, value: &[u8]) -> Result<()> {
+        self.map
+            .lock()
+            .expect("lock poisoned")
+            .insert(key.as_ref().to_vec(), value.to_vec());
+        Ok(())
+    }
+
+    async fn read(&self, key: &dyn Key) -> Result<Option<Vec<u8>>> {
+        Ok(self.map.lock().expect("lock poisoned").get(key.as_ref()).cloned())
+    }
+
+    async fn delete(&self, key: &dyn Key) -> Result<()> {
+        self.map.lock().expect("lock poisoned").remove(key.as_ref());
+        Ok(())
+    }
+
+    async fn iter_prefix(&self, prefix: &dyn Key) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let prefix = prefix.as_ref();
+        Ok(self
+            .map
+            .lock()
+            .expect("lock poisoned")
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ethers::core::types::H256;
+
+    use super::*;
+    use crate::db::AbacusDB;
+
+    fn sample_message() -> AbacusMessage {
+        AbacusMessage {
+            version: 0,
+            nonce: 1,
+            origin: 1000,
+            sender: H256::zero(),
+            destination: 2000,
+            recipient: H256::repeat_byte(0xAB),
+            body: vec![1, 2, 3, 4],
+        }
+    }
+
+    #[tokio::test]
+    async fn message_round_trips_through_abacus_db() {
+        let db = AbacusDB::from_store(Arc::new(MemoryKeyValueStore::new()));
+        let message = sample_message();
+
+        db.store_message(0, &message).await.unwrap();
+
+        let retrieved: AbacusMessage = db.message_by_leaf_index(0).await.unwrap().unwrap();
+        assert_eq!(retrieved, message);
+        assert_eq!(db.leaf_count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn memory_store_round_trips_and_respects_prefix() {
+        let store = MemoryKeyValueStore::new();
+        store.write(&b"message_0".to_vec(), b"a").await.unwrap();
+        store.write(&b"message_1".to_vec(), b"b").await.unwrap();
+        store.write(&b"other_0".to_vec(), b"c").await.unwrap();
+
+        assert_eq!(
+            store.read(&b"message_0".to_vec()).await.unwrap(),
+            Some(b"a".to_vec())
+        );
+
+        let mut prefixed = store.iter_prefix(&b"message_".to_vec()).await.unwrap();
+        prefixed.sort();
+        assert_eq!(
+            prefixed,
+            vec![
+                (b"message_0".to_vec(), b"a".to_vec()),
+                (b"message_1".to_vec(), b"b".to_vec()),
+            ]
+        );
+
+        store.delete(&b"message_0".to_vec()).await.unwrap();
+        assert_eq!(store.read(&b"message_0".to_vec()).await.unwrap(), None);
+    }
+
+    #[test]
+    fn u32_encode_decode_round_trips() {
+        let value = 42u32;
+        assert_eq!(u32::read_from(&value.to_vec()).unwrap(), value);
+    }
+}
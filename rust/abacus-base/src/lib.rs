@@ -0,0 +1,18 @@
+/// Abort a spawned task, ignoring the outcome.
+macro_rules! cancel_task {
+    ($task:expr) => {
+        $task.into_inner().abort()
+    };
+}
+
+pub mod chains;
+pub mod checkpoint;
+pub mod contract_sync;
+pub mod db;
+pub mod fee_oracle;
+pub mod kv_store;
+pub mod mailbox;
+pub mod reconnect;
+
+pub use contract_sync::{ContractSync, ContractSyncMetrics};
+pub use mailbox::CachingMailbox;
@@ -1,30 +1,45 @@
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_stream::stream;
 use async_trait::async_trait;
 use ethers::core::types::H256;
 use ethers::types::U256;
 use eyre::Result;
 use futures_util::future::select_all;
+use futures_util::Stream;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tracing::instrument::Instrumented;
 use tracing::{info_span, Instrument};
 
-use abacus_core::db::AbacusDB;
 use abacus_core::{
     AbacusChain, AbacusContract, AbacusMessage, ChainCommunicationError, Checkpoint, Mailbox,
     MailboxIndexer, TxCostEstimate, TxOutcome,
 };
 
 use crate::chains::IndexSettings;
+use crate::db::AbacusDB;
+use crate::fee_oracle::GasOracle;
+use crate::kv_store::KeyValueStore;
+use crate::reconnect::ReconnectableMailbox;
 use crate::{ContractSync, ContractSyncMetrics};
 
 /// Caching Mailbox type
 #[derive(Debug, Clone)]
 pub struct CachingMailbox {
-    mailbox: Arc<dyn Mailbox>,
+    mailbox: Arc<dyn ReconnectableMailbox>,
     db: AbacusDB,
     indexer: Arc<dyn MailboxIndexer>,
+    /// Signals the current leaf count (one past the highest leaf index)
+    /// whenever `sync` commits new leaves, so `subscribe` callers wake
+    /// immediately instead of polling.
+    new_message_tx: watch::Sender<u32>,
+    // Kept alive so `new_message_tx.send` never fails with "no receivers" in
+    // the common case where nobody has called `subscribe` yet.
+    _new_message_rx: watch::Receiver<u32>,
+    fee_oracle: Option<Arc<GasOracle>>,
 }
 
 impl std::fmt::Display for CachingMailbox {
@@ -35,16 +50,42 @@ impl std::fmt::Display for CachingMailbox {
 
 impl CachingMailbox {
     /// Instantiate new CachingMailbox
-    pub fn new(mailbox: Arc<dyn Mailbox>, db: AbacusDB, indexer: Arc<dyn MailboxIndexer>) -> Self {
+    pub fn new(
+        mailbox: Arc<dyn ReconnectableMailbox>,
+        db: AbacusDB,
+        indexer: Arc<dyn MailboxIndexer>,
+    ) -> Self {
+        let (new_message_tx, _new_message_rx) = watch::channel(0);
         Self {
             mailbox,
             db,
             indexer,
+            new_message_tx,
+            _new_message_rx,
+            fee_oracle: None,
         }
     }
 
+    /// Instantiate a new CachingMailbox whose [`AbacusDB`] wraps an arbitrary
+    /// [`KeyValueStore`], e.g. an in-memory store for tests or a remote KV
+    /// store.
+    pub fn new_with_store(
+        mailbox: Arc<dyn ReconnectableMailbox>,
+        store: Arc<dyn KeyValueStore>,
+        indexer: Arc<dyn MailboxIndexer>,
+    ) -> Self {
+        Self::new(mailbox, AbacusDB::from_store(store), indexer)
+    }
+
+    /// Attach a [`GasOracle`] so [`suggest_eip1559_fees`](Self::suggest_eip1559_fees)
+    /// can compute fee suggestions.
+    pub fn with_fee_oracle(mut self, fee_oracle: Arc<GasOracle>) -> Self {
+        self.fee_oracle = Some(fee_oracle);
+        self
+    }
+
     /// Return handle on mailbox object
-    pub fn mailbox(&self) -> &Arc<dyn Mailbox> {
+    pub fn mailbox(&self) -> &Arc<dyn ReconnectableMailbox> {
         &self.mailbox
     }
 
@@ -53,6 +94,17 @@ impl CachingMailbox {
         &self.db
     }
 
+    /// Suggest `(max_priority_fee_per_gas, max_fee_per_gas)` for a
+    /// transaction to be submitted imminently, via the attached
+    /// [`GasOracle`].
+    pub async fn suggest_eip1559_fees(&self) -> Result<(U256, U256)> {
+        let fee_oracle = self
+            .fee_oracle
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("no fee oracle configured for this mailbox"))?;
+        fee_oracle.suggest_eip1559_fees().await
+    }
+
     /// Spawn a task that syncs the CachingMailbox's db with the on-chain event
     /// data
     pub fn sync(
@@ -68,10 +120,18 @@ impl CachingMailbox {
             self.indexer.clone(),
             index_settings,
             metrics,
+            self.new_message_tx.clone(),
         );
 
+        let health_check = tokio::spawn(Self::connectivity_health_check(
+            self.mailbox.clone(),
+            index_settings.health_check_interval,
+            index_settings.health_check_failure_threshold,
+        ))
+        .instrument(info_span!("MailboxHealthCheck", self = %self));
+
         tokio::spawn(async move {
-            let tasks = vec![sync.sync_dispatched_messages()];
+            let tasks = vec![sync.sync_dispatched_messages(), health_check];
 
             let (_, _, remaining) = select_all(tasks).await;
             for task in remaining.into_iter() {
@@ -82,6 +142,86 @@ impl CachingMailbox {
         })
         .instrument(span)
     }
+
+    /// Periodically ping `mailbox` to detect a silently dead RPC connection,
+    /// reconnecting its transport after `failure_threshold` consecutive
+    /// failures.
+    async fn connectivity_health_check(
+        mailbox: Arc<dyn ReconnectableMailbox>,
+        check_interval: Duration,
+        failure_threshold: u32,
+    ) -> Result<()> {
+        let mut interval = tokio::time::interval(check_interval);
+        let mut consecutive_failures = 0u32;
+
+        loop {
+            interval.tick().await;
+
+            match mailbox.count().await {
+                Ok(_) => consecutive_failures = 0,
+                Err(error) => {
+                    consecutive_failures += 1;
+                    tracing::warn!(
+                        %error,
+                        consecutive_failures,
+                        failure_threshold,
+                        "Connectivity health check failed"
+                    );
+
+                    if should_reconnect(consecutive_failures, failure_threshold) {
+                        tracing::error!(consecutive_failures, "Reconnecting mailbox transport");
+                        mailbox.reconnect().await?;
+                        consecutive_failures = 0;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Subscribe to newly dispatched messages as they're committed by the
+    /// `sync` task, rather than polling `db` for them.
+    ///
+    /// If `from_leaf_index` is given, the returned stream first replays any
+    /// messages already in `db` starting at that index, so no messages are
+    /// missed between subscribing and the first notification.
+    pub fn subscribe(&self, from_leaf_index: Option<u32>) -> impl Stream<Item = AbacusMessage> {
+        let db = self.db.clone();
+        let mut rx = self.new_message_tx.subscribe();
+
+        stream! {
+            // The channel holds 0 until `sync` commits in this process, so
+            // also count the leaves already stored in `db`.
+            let stored_leaf_count = db.leaf_count().await.unwrap_or(0);
+            let mut next_leaf_index =
+                from_leaf_index.unwrap_or_else(|| stored_leaf_count.max(*rx.borrow()));
+            loop {
+                let latest_leaf_index = (*rx.borrow()).max(stored_leaf_count);
+                while next_leaf_index < latest_leaf_index {
+                    match db.message_by_leaf_index::<AbacusMessage>(next_leaf_index).await {
+                        Ok(Some(message)) => yield message,
+                        Ok(None) => break,
+                        Err(error) => {
+                            tracing::error!(%error, leaf_index = next_leaf_index, "Failed to load message for subscriber");
+                            break;
+                        }
+                    }
+                    next_leaf_index += 1;
+                }
+
+                if rx.changed().await.is_err() {
+                    // The sync task has been dropped; no more messages will
+                    // ever be signalled.
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Whether a reconnect should be attempted given the current run of
+/// consecutive health-check failures.
+fn should_reconnect(consecutive_failures: u32, failure_threshold: u32) -> bool {
+    consecutive_failures >= failure_threshold
 }
 
 #[async_trait]
@@ -148,3 +288,15 @@ impl AbacusContract for CachingMailbox {
         self.mailbox.address()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnects_once_failures_reach_the_threshold() {
+        assert!(!should_reconnect(2, 3));
+        assert!(should_reconnect(3, 3));
+        assert!(should_reconnect(4, 3));
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::core::types::H256;
+use ethers::types::U256;
+use eyre::{eyre, Result};
+
+use abacus_core::{
+    AbacusChain, AbacusContract, AbacusMessage, ChainCommunicationError, Checkpoint, Mailbox,
+    TxCostEstimate, TxOutcome,
+};
+
+/// A [`Mailbox`] whose underlying transport can be reestablished after a
+/// health check detects it has died.
+#[async_trait]
+pub trait ReconnectableMailbox: Mailbox {
+    /// Reestablish the underlying connection.
+    async fn reconnect(&self) -> Result<()>;
+}
+
+/// Adapts a plain [`Arc<dyn Mailbox>`] into a [`ReconnectableMailbox`] for
+/// mailbox implementations that can't yet reestablish their own connection;
+/// `reconnect` always returns an error rather than silently doing nothing.
+#[derive(Debug, Clone)]
+pub struct StaticMailbox(Arc<dyn Mailbox>);
+
+impl StaticMailbox {
+    /// Wrap `mailbox`, which does not support reconnecting.
+    pub fn new(mailbox: Arc<dyn Mailbox>) -> Self {
+        Self(mailbox)
+    }
+}
+
+#[async_trait]
+impl Mailbox for StaticMailbox {
+    fn local_domain_hash(&self) -> H256 {
+        self.0.local_domain_hash()
+    }
+
+    async fn count(&self) -> Result<u32, ChainCommunicationError> {
+        self.0.count().await
+    }
+
+    async fn delivered(&self, id: H256) -> Result<bool, ChainCommunicationError> {
+        self.0.delivered(id).await
+    }
+
+    async fn latest_checkpoint(
+        &self,
+        maybe_lag: Option<u64>,
+    ) -> Result<Checkpoint, ChainCommunicationError> {
+        self.0.latest_checkpoint(maybe_lag).await
+    }
+
+    async fn default_ism(&self) -> Result<H256, ChainCommunicationError> {
+        self.0.default_ism().await
+    }
+
+    async fn process(
+        &self,
+        message: &AbacusMessage,
+        metadata: &[u8],
+        tx_gas_limit: Option<U256>,
+    ) -> Result<TxOutcome, ChainCommunicationError> {
+        self.0.process(message, metadata, tx_gas_limit).await
+    }
+
+    async fn process_estimate_costs(
+        &self,
+        message: &AbacusMessage,
+        metadata: &[u8],
+    ) -> Result<TxCostEstimate> {
+        self.0.process_estimate_costs(message, metadata).await
+    }
+
+    fn process_calldata(&self, message: &AbacusMessage, metadata: &[u8]) -> Vec<u8> {
+        self.0.process_calldata(message, metadata)
+    }
+}
+
+impl AbacusChain for StaticMailbox {
+    fn chain_name(&self) -> &str {
+        self.0.chain_name()
+    }
+
+    fn local_domain(&self) -> u32 {
+        self.0.local_domain()
+    }
+}
+
+impl AbacusContract for StaticMailbox {
+    fn address(&self) -> H256 {
+        self.0.address()
+    }
+}
+
+#[async_trait]
+impl ReconnectableMailbox for StaticMailbox {
+    async fn reconnect(&self) -> Result<()> {
+        Err(eyre!(
+            "{} mailbox does not support reconnecting",
+            self.0.chain_name()
+        ))
+    }
+}